@@ -1,7 +1,17 @@
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::parse_macro_input;
 
+/// Keywords for the `then sort by <key>` / `group by <key>` transform qualifiers
+/// (GHC's `TransformListComp`). None of these are reserved Rust keywords, so
+/// they're introduced as custom keywords rather than `syn::Token![...]`.
+mod kw {
+    syn::custom_keyword!(then);
+    syn::custom_keyword!(sort);
+    syn::custom_keyword!(group);
+    syn::custom_keyword!(by);
+}
+
 /// Iterator comprehension
 ///
 /// The syntax is similar to [Haskell's list comprehension](https://wiki.haskell.org/List_comprehension).
@@ -57,20 +67,170 @@ use syn::parse_macro_input;
 /// iter![1; true];  // => [1]
 /// ```
 ///
+/// Qualifiers can be split into groups with `|`. Instead of nesting (a Cartesian
+/// product), the groups are zipped element-wise, stopping at the shortest one
+/// (similar to GHC's `ParallelListComp` extension).
+///
+/// ```
+/// # use comprehension::iter;
+/// iter![(x, y); x <- 0..3 | y <- 10..].collect::<Vec<_>>();
+/// // => [(0, 10), (1, 11), (2, 12)]
+/// ```
+///
+/// `then sort by <key>` sorts everything bound so far by `<key>` before
+/// continuing (GHC's `TransformListComp`).
+///
+/// ```
+/// # use comprehension::iter;
+/// iter![x; x <- vec![3, 1, 2], then sort by x].collect::<Vec<_>>();
+/// // => [1, 2, 3]
+/// ```
+///
+/// `group by <key>` groups everything bound so far by `<key>` and rebinds each
+/// of those names to a `Vec` of its values within the group, one group per
+/// distinct key (in order of first appearance).
+///
+/// ```
+/// # use comprehension::iter;
+/// iter![(key[0], x); x <- 1..=6, let key = x % 2, group by key].collect::<Vec<_>>();
+/// // => [(1, vec![1, 3, 5]), (0, vec![2, 4, 6])]
+/// ```
+///
 #[proc_macro]
 pub fn iter(item: TokenStream) -> TokenStream {
     let comp = parse_macro_input!(item as Comprehension);
+    comprehension_iter(&comp).into()
+}
 
-    let body = comp.body;
-    let mut ret = quote! {
-        std::iter::once(#body)
-    };
+/// Which iterator pipeline a [`Comprehension`] lowers to. The structural
+/// lowering (nesting order, zipping of parallel groups, scoping of `let`s) is
+/// identical either way; only the adaptors named at each `Qual` differ.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Backend {
+    /// `std::iter` (the only backend before the `rayon` feature existed).
+    Sequential,
+    /// `rayon::iter` pipeline, behind the `rayon` feature.
+    #[cfg(feature = "rayon")]
+    Rayon,
+}
+
+/// Lowers a [`Comprehension`] down to a sequential `std::iter` pipeline
+/// expression. Shared by the `iter!` macro itself and by macros (`map!`) that
+/// build a comprehension body out of their own surface syntax.
+fn comprehension_iter(comp: &Comprehension) -> proc_macro2::TokenStream {
+    comprehension_iter_backend(comp, Backend::Sequential)
+}
 
-    for q in comp.quals.iter().rev() {
+fn comprehension_iter_backend(comp: &Comprehension, backend: Backend) -> proc_macro2::TokenStream {
+    let body = &comp.body;
+    if comp.quals.len() == 1 {
+        lower_quals(&comp.quals[0], quote! { std::iter::once(#body) }, backend)
+    } else {
+        let group_iters: Vec<_> = comp
+            .quals
+            .iter()
+            .map(|group| {
+                let tuple = bindings_tuple(group);
+                lower_quals(group, quote! { std::iter::once(#tuple) }, backend)
+            })
+            .collect();
+
+        let mut pat = bindings_tuple(&comp.quals[0]);
+        for group in &comp.quals[1..] {
+            let group_pat = bindings_tuple(group);
+            pat = quote! { (#pat, #group_pat) };
+        }
+
+        match backend {
+            Backend::Sequential => {
+                let mut zipped = group_iters[0].clone();
+                for group_iter in &group_iters[1..] {
+                    zipped = quote! { (#zipped).zip(#group_iter) };
+                }
+                quote! {
+                    (#zipped).flat_map(move |#pat| std::iter::once(#body))
+                }
+            }
+            // `ParallelIterator::zip` requires `IndexedParallelIterator`, which the
+            // `flat_map_iter`-built group iterators above don't implement; collect
+            // each group into a `Vec` first to get back to something indexed
+            // (`Vec::into_par_iter`) that can be zipped.
+            #[cfg(feature = "rayon")]
+            Backend::Rayon => {
+                let first = &group_iters[0];
+                let mut zipped = quote! { (#first).collect::<Vec<_>>().into_par_iter() };
+                for group_iter in &group_iters[1..] {
+                    zipped = quote! {
+                        (#zipped).zip((#group_iter).collect::<Vec<_>>().into_par_iter())
+                    };
+                }
+                quote! {
+                    (#zipped).flat_map_iter(move |#pat| std::iter::once(#body))
+                }
+            }
+        }
+    }
+}
+
+/// Folds a single (non-parallel) group of qualifiers around `innermost`, from
+/// the last qualifier to the first, targeting the given [`Backend`]. Splits
+/// out `then sort by`/`group by` transforms (see [`lower_transform`]) first,
+/// since those need to materialize everything bound before them into a `Vec`
+/// before continuing.
+fn lower_quals(
+    quals: &[Qual],
+    innermost: proc_macro2::TokenStream,
+    backend: Backend,
+) -> proc_macro2::TokenStream {
+    if let Some(pos) = quals
+        .iter()
+        .position(|q| matches!(q, Qual::Transform(_) | Qual::GroupBy(_)))
+    {
+        return lower_transform(quals, pos, innermost, backend);
+    }
+    lower_quals_plain(quals, innermost, backend)
+}
+
+/// Folds a transform-free run of qualifiers around `innermost`, from the last
+/// qualifier to the first, targeting the given [`Backend`]. The outermost
+/// qualifier (index `0`, the last one folded) is where a `Rayon` backend
+/// actually forks work across the thread pool; everything nested inside it
+/// still flattens each item in turn.
+#[cfg_attr(not(feature = "rayon"), allow(unused_variables))]
+fn lower_quals_plain(
+    quals: &[Qual],
+    innermost: proc_macro2::TokenStream,
+    backend: Backend,
+) -> proc_macro2::TokenStream {
+    let mut ret = innermost;
+    for (i, q) in quals.iter().enumerate().rev() {
+        let outermost = i == 0;
         match q {
-            Qual::Generator(pat, iter) => {
-                ret = quote! {
-                    (#iter).into_iter().flat_map(move |#pat| #ret)
+            Qual::Generator(pat, iter, fallible) => {
+                let iter = if *fallible {
+                    quote! { (#iter)? }
+                } else {
+                    quote! { (#iter) }
+                };
+                ret = match backend {
+                    Backend::Sequential => quote! {
+                        #iter.into_iter().flat_map(move |#pat| #ret)
+                    },
+                    // Only the outermost qualifier forks across the thread pool; once
+                    // we're inside its closure there's no further forking, so nested
+                    // qualifiers fall back to plain `std::iter` combinators. Rayon's
+                    // `flat_map`'s closure must return something that is *itself* an
+                    // `IntoParallelIterator`, which a `std::iter` chain built by the
+                    // non-outermost arm below is not — hence `flat_map_iter` here,
+                    // whose closure only needs to return a plain `IntoIterator`.
+                    #[cfg(feature = "rayon")]
+                    Backend::Rayon if outermost => quote! {
+                        #iter.into_par_iter().flat_map_iter(move |#pat| #ret)
+                    },
+                    #[cfg(feature = "rayon")]
+                    Backend::Rayon => quote! {
+                        #iter.into_iter().flat_map(move |#pat| #ret)
+                    },
                 };
             }
             Qual::LocalDecl(expr_let) => {
@@ -82,76 +242,590 @@ pub fn iter(item: TokenStream) -> TokenStream {
                 };
             }
             Qual::Guard(pred) => {
-                ret = quote! {
-                    std::iter::once(())
-                        .take(if #pred {1} else {0})
-                        .flat_map(move |_| #ret)
+                ret = match backend {
+                    Backend::Sequential => quote! {
+                        std::iter::once(())
+                            .take(if #pred {1} else {0})
+                            .flat_map(move |_| #ret)
+                    },
+                    // Same reasoning as the `Generator` arm above: only fork with
+                    // `rayon::iter::once` when this guard is the outermost qualifier;
+                    // otherwise stay on plain `std::iter` combinators.
+                    #[cfg(feature = "rayon")]
+                    Backend::Rayon if outermost => quote! {
+                        rayon::iter::once(())
+                            .filter(move |_| #pred)
+                            .flat_map_iter(move |_| #ret)
+                    },
+                    #[cfg(feature = "rayon")]
+                    Backend::Rayon => quote! {
+                        std::iter::once(())
+                            .take(if #pred {1} else {0})
+                            .flat_map(move |_| #ret)
+                    },
                 }
             }
+            Qual::Transform(_) | Qual::GroupBy(_) => {
+                unreachable!("transforms are split out by `lower_quals` before reaching here")
+            }
         }
     }
-    ret.into()
+    ret
+}
+
+/// Lowers `quals[..pos]`, the `then sort by`/`group by` transform at `quals[pos]`,
+/// and `quals[pos + 1..]` (which may contain further transforms, handled by
+/// recursing back into [`lower_quals`]).
+///
+/// Everything bound by `quals[..pos]` is collected into a `Vec` of tuples (one
+/// tuple per combination produced so far), sorted or grouped by `key`, and then
+/// iterated again — so the rest of the comprehension continues from there.
+/// `group by` additionally "transposes" each group: every name bound before it
+/// is rebound to a `Vec` of its former values, one entry per item of the group
+/// (mirroring Haskell's `TransformListComp` semantics). Because of this
+/// transposition, `group by` only supports prefixes whose generators and
+/// `let`s bind a single identifier each (not arbitrary nested patterns); `then
+/// sort by` has no such restriction.
+fn lower_transform(
+    quals: &[Qual],
+    pos: usize,
+    innermost: proc_macro2::TokenStream,
+    backend: Backend,
+) -> proc_macro2::TokenStream {
+    let prefix = &quals[..pos];
+    let suffix = &quals[pos + 1..];
+
+    let bound = bindings_tuple(prefix);
+    let prefix_iter = lower_quals_plain(prefix, quote! { std::iter::once(#bound) }, backend);
+    let rest = lower_quals(suffix, innermost, backend);
+
+    match &quals[pos] {
+        Qual::Transform(key) => quote! {
+            {
+                let mut __comprehension_sorted: Vec<_> = (#prefix_iter).collect();
+                __comprehension_sorted.sort_by_key(|__comprehension_item| {
+                    #[allow(unused_variables)]
+                    let #bound = __comprehension_item.clone();
+                    #key
+                });
+                #[allow(unused_variables)]
+                __comprehension_sorted.into_iter().flat_map(move |#bound| #rest)
+            }
+        },
+        Qual::GroupBy(key) => {
+            let names = match group_by_names(prefix) {
+                Ok(names) => names,
+                Err(err) => return err.to_compile_error(),
+            };
+            let cols: Vec<_> = (0..names.len())
+                .map(|i| format_ident!("__comprehension_col{}", i))
+                .collect();
+
+            quote! {
+                {
+                    let __comprehension_items: Vec<_> = (#prefix_iter).collect();
+                    let mut __comprehension_groups: Vec<(_, Vec<_>)> = Vec::new();
+                    for __comprehension_item in __comprehension_items {
+                        let __comprehension_key = {
+                            #[allow(unused_variables)]
+                            let #bound = __comprehension_item.clone();
+                            #key
+                        };
+                        match __comprehension_groups
+                            .iter_mut()
+                            .find(|(k, _)| *k == __comprehension_key)
+                        {
+                            Some((_, items)) => items.push(__comprehension_item),
+                            None => __comprehension_groups.push((__comprehension_key, vec![__comprehension_item])),
+                        }
+                    }
+                    __comprehension_groups.into_iter().flat_map(move |(_, __comprehension_group)| {
+                        #(let mut #cols: Vec<_> = Vec::new();)*
+                        for __comprehension_item in __comprehension_group {
+                            let #bound = __comprehension_item;
+                            #(#cols.push(#names);)*
+                        }
+                        let (#(#names,)*) = (#(#cols,)*);
+                        #rest
+                    })
+                }
+            }
+        }
+        _ => unreachable!("lower_transform is only called on a Transform/GroupBy qualifier"),
+    }
+}
+
+/// Names bound by the generators and `let`s in a `group by`'s prefix, required
+/// for the "transpose into a `Vec` per name" step (see [`lower_transform`]).
+/// Unlike [`bindings_tuple`], this rejects any binding that isn't a plain
+/// identifier, since there would otherwise be no single name to collect each
+/// column's values under.
+fn group_by_names(prefix: &[Qual]) -> syn::Result<Vec<&syn::Ident>> {
+    prefix
+        .iter()
+        .filter_map(|q| match q {
+            Qual::Generator(pat, _, _) => Some(pat),
+            Qual::LocalDecl(expr_let) => Some(&*expr_let.pat),
+            Qual::Guard(_) | Qual::Transform(_) | Qual::GroupBy(_) => None,
+        })
+        .map(|pat| match pat {
+            syn::Pat::Ident(pat_ident) => Ok(&pat_ident.ident),
+            _ => Err(syn::Error::new_spanned(
+                pat,
+                "`group by` requires every binding before it to be a plain identifier",
+            )),
+        })
+        .collect()
+}
+
+/// Patterns bound by the generators and `let`s in a group of qualifiers, collected
+/// in order and wrapped as a tuple (used both as an expression and, when zipping
+/// parallel groups, as the destructuring pattern to recover those bindings).
+fn bindings_tuple(quals: &[Qual]) -> proc_macro2::TokenStream {
+    let pats = quals.iter().filter_map(|q| match q {
+        Qual::Generator(pat, _, _) => Some(pat),
+        Qual::LocalDecl(expr_let) => Some(&expr_let.pat),
+        Qual::Guard(_) | Qual::Transform(_) | Qual::GroupBy(_) => None,
+    });
+    quote! { (#(#pats,)*) }
+}
+
+/// How a try-comprehension (`try_iter!`/`try_vect!`, `opt_iter!`/`opt_vect!`)
+/// finishes: wrapping the accumulated `Vec` in `Ok` (short-circuiting via
+/// `Result`'s `?`) or in `Some` (short-circuiting via `Option`'s `?`).
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TryWrap {
+    Result,
+    Option,
+}
+
+impl TryWrap {
+    fn wrap(self, out: &syn::Ident) -> proc_macro2::TokenStream {
+        match self {
+            TryWrap::Result => quote! { Ok(#out) },
+            TryWrap::Option => quote! { Some(#out) },
+        }
+    }
+}
+
+/// Lowers a [`Comprehension`] to an eagerly-evaluated block that pushes each
+/// produced item onto a `Vec`, wrapped in an immediately-invoked closure that
+/// returns `Result<Vec<T>, E>` or `Option<Vec<T>>` (per `wrap`).
+///
+/// This is deliberately a *different* shape from [`comprehension_iter_backend`]:
+/// a fallible generator or `let` uses `?` to abort early, and `?` only works
+/// inside the function/closure it directly appears in. `comprehension_iter_backend`
+/// nests non-outermost qualifiers inside `flat_map` closures that return a plain
+/// iterator (not a `Result`/`Option`), so a `?` anywhere but the outermost
+/// qualifier can't compile there. Here every qualifier lowers to a `for`/`if`/
+/// `let` statement instead of an iterator adaptor, all still directly inside the
+/// one IIFE, so `?` works at any nesting depth.
+fn comprehension_try(comp: &Comprehension, wrap: TryWrap) -> proc_macro2::TokenStream {
+    let body = &comp.body;
+    let out = format_ident!("__comprehension_out");
+    let push = quote! { #out.push(#body); };
+
+    let loops = if comp.quals.len() == 1 {
+        try_lower_quals(&comp.quals[0], push)
+    } else {
+        let group_vars: Vec<_> = (0..comp.quals.len())
+            .map(|i| format_ident!("__comprehension_group{}", i))
+            .collect();
+
+        let group_setup = comp.quals.iter().zip(&group_vars).map(|(group, var)| {
+            let tuple = bindings_tuple(group);
+            let group_loops = try_lower_quals(group, quote! { #var.push(#tuple); });
+            quote! {
+                let mut #var: Vec<_> = Vec::new();
+                #group_loops
+            }
+        });
+
+        let mut zipped = {
+            let first = &group_vars[0];
+            quote! { #first.into_iter() }
+        };
+        for var in &group_vars[1..] {
+            zipped = quote! { (#zipped).zip(#var) };
+        }
+
+        let mut pat = bindings_tuple(&comp.quals[0]);
+        for group in &comp.quals[1..] {
+            let group_pat = bindings_tuple(group);
+            pat = quote! { (#pat, #group_pat) };
+        }
+
+        quote! {
+            #(#group_setup)*
+            for #pat in #zipped {
+                #push
+            }
+        }
+    };
+
+    let wrapped = wrap.wrap(&out);
+    quote! {
+        (|| {
+            let mut #out = Vec::new();
+            #loops
+            #wrapped
+        })()
+    }
+}
+
+/// Folds a single (non-parallel) group of qualifiers into nested `for`/`if`/
+/// `let` statements around `innermost`, splitting out `then sort by`/`group by`
+/// transforms the same way [`lower_quals`] does.
+fn try_lower_quals(quals: &[Qual], innermost: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    if let Some(pos) = quals
+        .iter()
+        .position(|q| matches!(q, Qual::Transform(_) | Qual::GroupBy(_)))
+    {
+        return try_lower_transform(quals, pos, innermost);
+    }
+    try_lower_plain(quals, innermost)
+}
+
+/// Folds a transform-free run of qualifiers into nested `for`/`if`/`let`
+/// statements around `innermost`.
+fn try_lower_plain(quals: &[Qual], innermost: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let mut ret = innermost;
+    for q in quals.iter().rev() {
+        ret = match q {
+            Qual::Generator(pat, iter, fallible) => {
+                let iter = if *fallible {
+                    quote! { (#iter)? }
+                } else {
+                    quote! { (#iter) }
+                };
+                quote! {
+                    for #pat in (#iter).into_iter() {
+                        #ret
+                    }
+                }
+            }
+            Qual::LocalDecl(expr_let) => quote! {
+                #expr_let;
+                #ret
+            },
+            Qual::Guard(pred) => quote! {
+                if #pred {
+                    #ret
+                }
+            },
+            Qual::Transform(_) | Qual::GroupBy(_) => {
+                unreachable!("transforms are split out by `try_lower_quals` before reaching here")
+            }
+        };
+    }
+    ret
+}
+
+/// Statement-based counterpart of [`lower_transform`]: materializes
+/// `quals[..pos]` into a `Vec` via [`try_lower_plain`], sorts/groups it by
+/// `key`, then continues with `quals[pos + 1..]` (via [`try_lower_quals`], so
+/// further transforms keep working) for each resulting item/group.
+fn try_lower_transform(
+    quals: &[Qual],
+    pos: usize,
+    innermost: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let prefix = &quals[..pos];
+    let suffix = &quals[pos + 1..];
+
+    let bound = bindings_tuple(prefix);
+    let collect = quote! { __comprehension_prefix.push(#bound); };
+    let prefix_loops = try_lower_plain(prefix, collect);
+    let rest = try_lower_quals(suffix, innermost);
+
+    match &quals[pos] {
+        Qual::Transform(key) => quote! {
+            {
+                let mut __comprehension_prefix: Vec<_> = Vec::new();
+                #prefix_loops
+                __comprehension_prefix.sort_by_key(|__comprehension_item| {
+                    #[allow(unused_variables)]
+                    let #bound = __comprehension_item.clone();
+                    #key
+                });
+                #[allow(unused_variables)]
+                for #bound in __comprehension_prefix {
+                    #rest
+                }
+            }
+        },
+        Qual::GroupBy(key) => {
+            let names = match group_by_names(prefix) {
+                Ok(names) => names,
+                Err(err) => return err.to_compile_error(),
+            };
+            let cols: Vec<_> = (0..names.len())
+                .map(|i| format_ident!("__comprehension_col{}", i))
+                .collect();
+
+            quote! {
+                {
+                    let mut __comprehension_prefix: Vec<_> = Vec::new();
+                    #prefix_loops
+                    let mut __comprehension_groups: Vec<(_, Vec<_>)> = Vec::new();
+                    for __comprehension_item in __comprehension_prefix {
+                        let __comprehension_key = {
+                            #[allow(unused_variables)]
+                            let #bound = __comprehension_item.clone();
+                            #key
+                        };
+                        match __comprehension_groups
+                            .iter_mut()
+                            .find(|(k, _)| *k == __comprehension_key)
+                        {
+                            Some((_, items)) => items.push(__comprehension_item),
+                            None => __comprehension_groups.push((__comprehension_key, vec![__comprehension_item])),
+                        }
+                    }
+                    for (_, __comprehension_group) in __comprehension_groups {
+                        #(let mut #cols: Vec<_> = Vec::new();)*
+                        for __comprehension_item in __comprehension_group {
+                            let #bound = __comprehension_item;
+                            #(#cols.push(#names);)*
+                        }
+                        let (#(#names,)*) = (#(#cols,)*);
+                        #rest
+                    }
+                }
+            }
+        }
+        _ => unreachable!("try_lower_transform is only called on a Transform/GroupBy qualifier"),
+    }
 }
 
 struct Comprehension {
     body: syn::Expr,
-    quals: Vec<Qual>,
+    quals: Vec<Vec<Qual>>,
 }
 
 enum Qual {
-    Generator(syn::Pat, syn::Expr),
+    /// `pat <- expr`, or `pat <- expr?` when `fallible` is set (see `try_iter!`).
+    Generator(syn::Pat, syn::Expr, bool),
     LocalDecl(syn::ExprLet),
     Guard(syn::Expr),
+    /// `then sort by <key>` (GHC's `TransformListComp`).
+    Transform(syn::Expr),
+    /// `group by <key>` (GHC's `TransformListComp`).
+    GroupBy(syn::Expr),
 }
 
 impl syn::parse::Parse for Comprehension {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        use syn::{punctuated::Punctuated, Token};
-
         let body = input.parse()?;
         input.parse::<syn::Token![;]>()?;
-        let quals = Punctuated::<Qual, Token![,]>::parse_terminated(input)?
-            .into_iter()
-            .collect();
+        let quals = parse_qual_groups(input)?;
         Ok(Comprehension { body, quals })
     }
 }
 
+/// Parses the `<quals>, ... | <quals>, ...` qualifier groups shared by any
+/// macro built on top of [`Comprehension`] (after its own head syntax, up to
+/// and including the `;`, has already been consumed).
+fn parse_qual_groups(input: syn::parse::ParseStream) -> syn::Result<Vec<Vec<Qual>>> {
+    use syn::Token;
+
+    let mut quals = vec![parse_qual_group(input)?];
+    while input.peek(Token![|]) {
+        input.parse::<Token![|]>()?;
+        quals.push(parse_qual_group(input)?);
+    }
+    Ok(quals)
+}
+
+/// Parses a comma-separated run of qualifiers, stopping at a top-level `|`
+/// (the separator between parallel generator groups) or the end of input.
+fn parse_qual_group(input: syn::parse::ParseStream) -> syn::Result<Vec<Qual>> {
+    use syn::Token;
+
+    let mut quals = Vec::new();
+    while !input.is_empty() && !input.peek(Token![|]) {
+        quals.push(input.parse::<Qual>()?);
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        } else {
+            break;
+        }
+    }
+    Ok(quals)
+}
+
 impl syn::parse::Parse for Qual {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        parse_generator(input)
-            .or_else(|_| parse_local_decl(input))
-            .or_else(|_| parse_guard(input))
+        if input.peek(kw::then) {
+            return parse_transform(input);
+        }
+
+        if input.peek(kw::group) {
+            return parse_group_by(input);
+        }
+
+        if input.peek(syn::Token![let]) {
+            return parse_local_decl(input);
+        }
+
+        let looks_like_generator = {
+            let fork = input.fork();
+            syn::Pat::parse_single(&fork)
+                .and_then(|_| fork.parse::<syn::Token![<-]>())
+                .is_ok()
+        };
+        if looks_like_generator {
+            return parse_generator(input);
+        }
+
+        // If there's a pattern followed by a lone `<` and then a range, the most
+        // likely explanation is a typo'd or incomplete generator arrow (e.g.
+        // `x < 0..10` instead of `x <- 0..10`). This has to be checked *before*
+        // the generic guard-expr fallback below: comparisons bind tighter than
+        // `..`, so `x < 0..10` parses just fine as a standalone expr
+        // (`(x < 0)..10`), which would otherwise silently produce a confusing
+        // `bool`-vs-`Range` type error downstream instead of naming the actual
+        // mistake. A `<` followed by anything other than a range is left alone.
+        let fork = input.fork();
+        if syn::Pat::parse_single(&fork).is_ok() && fork.peek(syn::Token![<]) {
+            let arrow_span = fork.span();
+            let rhs_is_range = fork.parse::<syn::Token![<]>().is_ok()
+                && matches!(parse_expr_before_qual_sep(&fork), Ok(syn::Expr::Range(_)));
+            if rhs_is_range {
+                return Err(syn::Error::new(
+                    arrow_span,
+                    "expected a generator arrow `<-` here; did you mean `<pat> <- <expr>`?",
+                ));
+            }
+        }
+
+        // A genuine guard (e.g. `x < 10`) must parse as a standalone `bool` expr.
+        if input.fork().parse::<syn::Expr>().is_ok() {
+            return parse_guard(input);
+        }
+
+        parse_guard(input)
     }
 }
 
+/// Parses an expression, stopping *before* a top-level `,` or `|` instead of
+/// letting `syn::Expr` greedily consume it. Plain `input.parse::<syn::Expr>()`
+/// treats `|` as the bitor operator, so e.g. `x <- xs | y <- ys` would parse
+/// as the single generator `x <- (xs | y) < (-ys)` instead of splitting into
+/// two parallel groups. `||` and `|=` are still recognized and consumed as
+/// part of the expression; only a standalone `|` is treated as the group
+/// separator, matching what `parse_qual_group` peeks for afterwards.
+fn parse_expr_before_qual_sep(input: syn::parse::ParseStream) -> syn::Result<syn::Expr> {
+    use proc_macro2::{Spacing, TokenTree};
+
+    let tokens = input.step(|cursor| {
+        let mut rest = *cursor;
+        let mut collected = proc_macro2::TokenStream::new();
+        // Turbofish generic args (`::<A, B>`) aren't a delimited `Group` at the
+        // token level, so a bare comma inside them (e.g. `.collect::<Result<Vec<_>, _>>()`)
+        // would otherwise look just like the comma between two qualifiers. Track
+        // whether we're inside one (opened by a `<` right after `::`) so those
+        // commas — and any stray `|`, `>` while we're in there — aren't mistaken
+        // for a qualifier/group separator.
+        let mut angle_depth = 0i32;
+        let mut last_was_colon = false;
+        loop {
+            let Some((tt, next)) = rest.token_tree() else {
+                return Ok((collected, rest));
+            };
+            if let TokenTree::Punct(punct) = &tt {
+                match punct.as_char() {
+                    ',' if angle_depth == 0 => return Ok((collected, rest)),
+                    // Once we're inside a turbofish, any further `<` nests another
+                    // level of generic args (e.g. the `Vec<_>` inside `::<Result<Vec<_>, _>>`)
+                    // without needing its own `::` — only the outermost one does.
+                    '<' if last_was_colon || angle_depth > 0 => angle_depth += 1,
+                    '>' if angle_depth > 0 => angle_depth -= 1,
+                    '|' if angle_depth == 0 => {
+                        if punct.spacing() == Spacing::Joint {
+                            if let Some((tt2, next2)) = next.token_tree() {
+                                let compound = matches!(
+                                    &tt2,
+                                    TokenTree::Punct(p2) if p2.as_char() == '|' || p2.as_char() == '='
+                                );
+                                if compound {
+                                    collected.extend([tt, tt2]);
+                                    rest = next2;
+                                    last_was_colon = false;
+                                    continue;
+                                }
+                            }
+                        }
+                        return Ok((collected, rest));
+                    }
+                    _ => {}
+                }
+                last_was_colon = punct.as_char() == ':';
+            } else {
+                last_was_colon = false;
+            }
+            collected.extend(std::iter::once(tt));
+            rest = next;
+        }
+    })?;
+    syn::parse2(tokens)
+}
+
 fn parse_generator(input: syn::parse::ParseStream) -> syn::Result<Qual> {
-    if {
-        let input = input.fork();
-        input
-            .parse::<syn::Pat>()
-            .and_then(|_| input.parse::<syn::Token![<-]>())
-            .is_ok()
-    } {
-        let pat = input.parse()?;
-        input.parse::<syn::Token![<-]>()?;
-        let expr = input.parse()?;
-        Ok(Qual::Generator(pat, expr))
-    } else {
-        Err(syn::Error::new(input.span(), "expect pat"))
-    }
+    let pat = syn::Pat::parse_single(input)?;
+    input.parse::<syn::Token![<-]>()?;
+    let expr: syn::Expr = parse_expr_before_qual_sep(input)?;
+    // `pat <- expr?` (used by `try_iter!`/`try_vect!`): a trailing `?` already
+    // parses as `syn::Expr::Try`, so peel it off and remember that this
+    // generator's source is fallible and must be unwrapped before iterating.
+    let (expr, fallible) = match expr {
+        syn::Expr::Try(try_expr) => (*try_expr.expr, true),
+        expr => (expr, false),
+    };
+    Ok(Qual::Generator(pat, expr, fallible))
 }
 
 fn parse_local_decl(input: syn::parse::ParseStream) -> syn::Result<Qual> {
-    if input.peek(syn::Token![let]) {
-        input.parse().map(Qual::LocalDecl)
-    } else {
-        Err(syn::Error::new(input.span(), "expect `let`"))
-    }
+    let let_span = input.span();
+    parse_expr_let(input).map(Qual::LocalDecl).map_err(|_| {
+        syn::Error::new(
+            let_span,
+            "`let` qualifier needs an initializer: `let <pat> = <expr>`",
+        )
+    })
+}
+
+/// Parses `let <pat> = <expr>`, same shape as `syn::ExprLet::parse`, except
+/// the initializer is parsed with `parse_expr_before_qual_sep` instead of the
+/// full `syn::Expr` grammar — otherwise a `let` immediately before a top-level
+/// `|` group separator (e.g. `..., let y = x * 2 | z <- zs`) would have its
+/// initializer greedily consume the `|` as bitor, same issue as generators
+/// and guards.
+fn parse_expr_let(input: syn::parse::ParseStream) -> syn::Result<syn::ExprLet> {
+    Ok(syn::ExprLet {
+        attrs: Vec::new(),
+        let_token: input.parse()?,
+        pat: Box::new(syn::Pat::parse_multi_with_leading_vert(input)?),
+        eq_token: input.parse()?,
+        expr: Box::new(parse_expr_before_qual_sep(input)?),
+    })
 }
 
 fn parse_guard(input: syn::parse::ParseStream) -> syn::Result<Qual> {
-    input.parse().map(Qual::Guard)
+    parse_expr_before_qual_sep(input).map(Qual::Guard)
+}
+
+fn parse_transform(input: syn::parse::ParseStream) -> syn::Result<Qual> {
+    input.parse::<kw::then>()?;
+    input.parse::<kw::sort>()?;
+    input.parse::<kw::by>()?;
+    input.parse().map(Qual::Transform)
+}
+
+fn parse_group_by(input: syn::parse::ParseStream) -> syn::Result<Qual> {
+    input.parse::<kw::group>()?;
+    input.parse::<kw::by>()?;
+    input.parse().map(Qual::GroupBy)
 }
 
 /// Vector comprehension
@@ -167,6 +841,183 @@ pub fn vect(item: TokenStream) -> TokenStream {
     ret.into()
 }
 
+/// Short-circuiting vector comprehension over `Result`
+///
+/// Like `vect!`, but a generator or `let` written with a trailing `?` (e.g.
+/// `<pat> <- <expr>?`, `let <pat> = <expr>?`) aborts the whole comprehension
+/// on the first error, instead of threading the `Result` through by hand.
+/// Evaluates eagerly to a `Result<Vec<T>, E>` (there is no lazy `try_iter!`
+/// pipeline step before this: a `?` only works inside the function/closure it
+/// directly appears in, and a non-outermost qualifier would otherwise have to
+/// fire its `?` inside a plain iterator-adaptor closure that isn't allowed to
+/// return `Result` at all, so every qualifier is instead lowered to a
+/// `for`/`if`/`let` statement inside one immediately-invoked closure).
+///
+/// ```
+/// # use comprehension::try_vect;
+/// fn parse_all(xs: &[&str]) -> Result<Vec<i32>, std::num::ParseIntError> {
+///     try_vect![n; s <- xs, let n = s.parse::<i32>()?]
+/// }
+/// assert_eq!(parse_all(&["1", "2", "3"]).unwrap(), vec![1, 2, 3]);
+/// assert!(parse_all(&["1", "x", "3"]).is_err());
+/// ```
+///
+#[proc_macro]
+pub fn try_vect(item: TokenStream) -> TokenStream {
+    let comp = parse_macro_input!(item as Comprehension);
+    comprehension_try(&comp, TryWrap::Result).into()
+}
+
+/// Alias for `try_vect!`, kept for symmetry with `iter!`/`vect!`.
+///
+/// `try_vect!` already evaluates eagerly to `Result<Vec<T>, E>` (see its docs
+/// for why), so there's no separate lazy-iterator step to additionally
+/// `.collect()`; `try_iter!` expands to exactly the same code.
+///
+#[proc_macro]
+pub fn try_iter(item: TokenStream) -> TokenStream {
+    try_vect(item)
+}
+
+/// Short-circuiting vector comprehension over `Option`
+///
+/// Same as `try_vect!`, but for generators/`let`s that short-circuit via
+/// `Option`'s `?` (e.g. `<pat> <- <expr>?` where `<expr>: Option<_>`) instead
+/// of `Result`'s, evaluating eagerly to `Option<Vec<T>>`.
+///
+/// ```
+/// # use comprehension::opt_vect;
+/// fn first_chars(xs: &[&str]) -> Option<Vec<char>> {
+///     opt_vect![c; s <- xs, let c = s.chars().next()?]
+/// }
+/// assert_eq!(first_chars(&["ab", "cd"]), Some(vec!['a', 'c']));
+/// assert_eq!(first_chars(&["ab", ""]), None);
+/// ```
+///
+#[proc_macro]
+pub fn opt_vect(item: TokenStream) -> TokenStream {
+    let comp = parse_macro_input!(item as Comprehension);
+    comprehension_try(&comp, TryWrap::Option).into()
+}
+
+/// Alias for `opt_vect!`, kept for symmetry with `iter!`/`vect!` (see
+/// `try_iter!`'s docs for why there's no separate lazy-iterator step).
+///
+#[proc_macro]
+pub fn opt_iter(item: TokenStream) -> TokenStream {
+    opt_vect(item)
+}
+
+/// Rayon-backed parallel iterator comprehension
+///
+/// Available behind the `rayon` feature. Same syntax as `iter!`, but the
+/// outermost generator (or guard) forks work across the thread pool
+/// (`into_par_iter().flat_map_iter(...)`); there's no further forking below
+/// that, so nested generators and guards fall back to plain `std::iter`
+/// combinators. Requires the calling crate to depend on `rayon` and bring
+/// `rayon::prelude::*` into scope. Worthwhile when the body is expensive over
+/// a large range, e.g. `par_vect![heavy(x); x <- 0..1_000_000]`.
+///
+/// `|`-separated parallel groups (see `iter!`'s docs) are supported too, but
+/// unlike `iter!`'s lazy, stops-at-the-shortest-group zip, every group is
+/// eagerly collected into a `Vec` before zipping (`rayon::iter::Zip` needs
+/// both sides to be an `IndexedParallelIterator`, which the thread-forking
+/// iterators built above aren't). So each group must be a bounded iterator —
+/// `x <- 0..5 | y <- 10..` does not compile, since each group's outermost
+/// generator calls `into_par_iter()` and rayon has no parallel iterator over an
+/// unbounded `10..`.
+///
+#[cfg(feature = "rayon")]
+#[proc_macro]
+pub fn par_iter(item: TokenStream) -> TokenStream {
+    let comp = parse_macro_input!(item as Comprehension);
+    comprehension_iter_backend(&comp, Backend::Rayon).into()
+}
+
+/// Rayon-backed parallel vector comprehension
+///
+/// `par_vect![...]` is same as `par_iter![...].collect::<Vec<_>>()`. Available
+/// behind the `rayon` feature.
+///
+#[cfg(feature = "rayon")]
+#[proc_macro]
+pub fn par_vect(item: TokenStream) -> TokenStream {
+    let body: proc_macro2::TokenStream = par_iter(item).into();
+    let ret = quote! {
+        #body.collect::<Vec<_>>()
+    };
+    ret.into()
+}
+
+/// Set comprehension
+///
+/// `set![...]` just is same as `iter![...].collect::<std::collections::HashSet<_>>()`
+///
+/// ```
+/// # use comprehension::set;
+/// let s = set![x % 3; x <- 0..10];
+/// // => {0, 1, 2}
+/// ```
+///
+#[proc_macro]
+pub fn set(item: TokenStream) -> TokenStream {
+    let body: proc_macro2::TokenStream = iter(item).into();
+    let ret = quote! {
+        #body.collect::<std::collections::HashSet<_>>()
+    };
+    ret.into()
+}
+
+/// Map comprehension
+///
+/// `map![k => v; quals...]` mirrors Python/Haskell dict comprehensions, collecting
+/// `(k, v)` pairs produced by the same qualifiers `iter!` understands into a
+/// `std::collections::HashMap`.
+///
+/// ```
+/// # use comprehension::map;
+/// let m = map![x => x * x; x <- 0..5];
+/// assert_eq!(m[&3], 9);
+/// ```
+///
+#[proc_macro]
+pub fn map(item: TokenStream) -> TokenStream {
+    let comp = parse_macro_input!(item as MapComprehension).0;
+    let body = comprehension_iter(&comp);
+    let ret = quote! {
+        {
+            fn map_helper<K, V, I>(it: I) -> std::collections::HashMap<K, V>
+            where
+                K: std::cmp::Eq + std::hash::Hash,
+                I: Iterator<Item = (K, V)>,
+            {
+                it.collect()
+            }
+            map_helper(#body)
+        }
+    };
+    ret.into()
+}
+
+/// Parses `map!`'s `<key> => <value>; <quals>` head and rewrites it into a plain
+/// [`Comprehension`] whose body is the tuple `(key, value)`, so the rest of the
+/// pipeline (qualifier parsing, nesting/zipping codegen) is shared with `iter!`.
+struct MapComprehension(Comprehension);
+
+impl syn::parse::Parse for MapComprehension {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let key: syn::Expr = input.parse()?;
+        input.parse::<syn::Token![=>]>()?;
+        let value: syn::Expr = input.parse()?;
+        input.parse::<syn::Token![;]>()?;
+        let quals = parse_qual_groups(input)?;
+        Ok(MapComprehension(Comprehension {
+            body: syn::parse_quote! { (#key, #value) },
+            quals,
+        }))
+    }
+}
+
 /// Sum of iterator comprehension
 ///
 /// `sum![...]` is same as `iter![...].sum()` excepting output type will be inferred.
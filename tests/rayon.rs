@@ -0,0 +1,32 @@
+#![cfg(feature = "rayon")]
+
+use comprehension::{par_iter, par_vect};
+use rayon::prelude::*;
+
+#[test]
+fn test_par() {
+    assert_eq!(
+        par_vect![x * x; x <- 0..10],
+        vec![0, 1, 4, 9, 16, 25, 36, 49, 64, 81],
+    );
+
+    assert_eq!(
+        par_vect![x * y; x <- 1..=3, y <- 1..=3],
+        vec![1, 2, 3, 2, 4, 6, 3, 6, 9],
+    );
+
+    assert_eq!(par_iter![x * x; x <- 0..10].sum::<i32>(), 285);
+}
+
+#[test]
+fn test_par_groups() {
+    assert_eq!(
+        par_vect![(x, y); x <- 0..5 | y <- 10..15],
+        vec![(0, 10), (1, 11), (2, 12), (3, 13), (4, 14)],
+    );
+
+    assert_eq!(
+        par_vect![x + y + z; x <- 0..3 | y <- 10..13 | z <- 100..103],
+        vec![110, 113, 116],
+    );
+}
@@ -108,4 +108,96 @@ fn test_iter() {
 
     let t = vect![(x, y); x <- 1..=3, y <- 1..=3];
     assert_eq!(vect![x * y; (x, y) <- t], vec![1, 2, 3, 2, 4, 6, 3, 6, 9]);
+
+    assert_eq!(
+        vect![(x, y); x <- 0..5 | y <- 10..],
+        vec![(0, 10), (1, 11), (2, 12), (3, 13), (4, 14)],
+    );
+
+    assert_eq!(
+        vect![(x, y); x <- 0..10, x % 2 == 0, | y <- 10..13],
+        vec![(0, 10), (2, 11), (4, 12)],
+    );
+
+    assert_eq!(
+        vect![x + y + z; x <- 0..3 | y <- 10..13 | z <- 100..103],
+        vec![110, 113, 116],
+    );
+
+    assert_eq!(
+        vect![(x, xn, y); x <- vec![1, 2, 3], let xn = x * 2 | y <- vec![10, 20, 30]],
+        vec![(1, 2, 10), (2, 4, 20), (3, 6, 30)],
+    );
+
+    assert_eq!(
+        vect![x; x <- vec![3, 1, 2], then sort by x],
+        vec![1, 2, 3],
+    );
+
+    assert_eq!(
+        vect![x; x <- vec![3, 1, 2], then sort by -x],
+        vec![3, 2, 1],
+    );
+
+    assert_eq!(
+        vect![(key[0], x); x <- 1..=6, let key = x % 2, group by key],
+        vec![(1, vec![1, 3, 5]), (0, vec![2, 4, 6])],
+    );
+
+    assert_eq!(
+        vect![(key[0], x, y); x <- vec![1, 2], y <- vec!['a', 'b'], let key = x, group by key],
+        vec![
+            (1, vec![1, 1], vec!['a', 'b']),
+            (2, vec![2, 2], vec!['a', 'b']),
+        ],
+    );
+}
+
+#[test]
+fn test_set() {
+    use std::collections::HashSet;
+
+    assert_eq!(
+        set![x % 3; x <- 0..10],
+        vec![0, 1, 2].into_iter().collect::<HashSet<_>>(),
+    );
+
+    assert_eq!(
+        set![x * y; x <- 1..=3, y <- 1..=3],
+        vec![1, 2, 3, 4, 6, 9].into_iter().collect::<HashSet<_>>(),
+    );
+}
+
+#[test]
+fn test_map() {
+    use std::collections::HashMap;
+
+    assert_eq!(
+        map![x => x * x; x <- 0..5],
+        vec![(0, 0), (1, 1), (2, 4), (3, 9), (4, 16)]
+            .into_iter()
+            .collect::<HashMap<_, _>>(),
+    );
+
+    assert_eq!(
+        map![k => v; (k, v) <- vec![("a", 1), ("b", 2)], v > 1],
+        vec![("b", 2)].into_iter().collect::<HashMap<_, _>>(),
+    );
+}
+
+#[test]
+fn test_try_iter() {
+    fn parse_all(xs: &[&str]) -> Result<Vec<i32>, std::num::ParseIntError> {
+        try_vect![n; s <- xs, let n = s.parse::<i32>()?]
+    }
+
+    assert_eq!(parse_all(&["1", "2", "3"]).unwrap(), vec![1, 2, 3]);
+    assert!(parse_all(&["1", "x", "3"]).is_err());
+
+    fn doubled_lines(xs: &[&str]) -> Result<Vec<i32>, std::num::ParseIntError> {
+        try_vect![n * 2; n <- xs.iter().map(|s| s.parse::<i32>()).collect::<Result<Vec<_>, _>>()?]
+    }
+
+    assert_eq!(doubled_lines(&["1", "2", "3"]).unwrap(), vec![2, 4, 6]);
+    assert!(doubled_lines(&["1", "x"]).is_err());
 }